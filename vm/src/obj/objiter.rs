@@ -2,13 +2,17 @@
  * Various types to support iteration.
  */
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
-use crate::pyobject::{PyContext, PyObjectRef, PyRef, PyResult, PyValue};
+use crate::pyobject::{IdProtocol, PyContext, PyObjectRef, PyRef, PyResult, PyValue};
 use crate::vm::VirtualMachine;
 
+use num_traits::ToPrimitive;
+
+use super::objbool;
 use super::objbytearray::PyByteArray;
 use super::objbytes::PyBytes;
+use super::objint;
 use super::objrange::PyRange;
 use super::objsequence;
 use super::objtype;
@@ -20,35 +24,66 @@ use super::objtype::PyClassRef;
  * function 'iter' is called.
  */
 pub fn get_iter(vm: &VirtualMachine, iter_target: &PyObjectRef) -> PyResult {
-    vm.call_method(iter_target, "__iter__", vec![])
-    // let type_str = objstr::get_value(&vm.to_str(iter_target.class()).unwrap());
-    // let type_error = vm.new_type_error(format!("Cannot iterate over {}", type_str));
-    // return Err(type_error);
-
-    // TODO: special case when iter_target only has __getitem__
-    // see: https://docs.python.org/3/library/functions.html#iter
-    // also https://docs.python.org/3.8/reference/datamodel.html#special-method-names
+    vm.call_method(iter_target, "__iter__", vec![]).or_else(|iter_error| {
+        // Only fall back to the old-style sequence protocol when __iter__ is
+        // simply missing (AttributeError). If __iter__ exists but raises
+        // something else, that's a real error from the object and must
+        // propagate, not be swallowed in favor of indexing via __getitem__.
+        // see: https://docs.python.org/3/library/functions.html#iter
+        if !objtype::isinstance(&iter_error, &vm.ctx.exceptions.attribute_error) {
+            return Err(iter_error);
+        }
+        if vm.get_attribute(iter_target.clone(), "__getitem__").is_ok() {
+            let iterator = PySequenceIterator {
+                position: Cell::new(0),
+                obj: iter_target.clone(),
+            };
+            Ok(iterator.into_ref(vm).into_object())
+        } else {
+            Err(iter_error)
+        }
+    })
 }
 
 pub fn call_next(vm: &VirtualMachine, iter_obj: &PyObjectRef) -> PyResult {
     vm.call_method(iter_obj, "__next__", vec![])
 }
 
+/// Outcome of advancing an iterator by one step.
+pub enum IterNextOutput {
+    /// The iterator yielded a value.
+    Value(PyObjectRef),
+    /// The iterator raised `StopIteration`, carrying the value it was given
+    /// (if any). Generators attach their `return` value this way (PEP 380),
+    /// which `yield from` and coroutine drivers need to see.
+    StopIteration(Option<PyObjectRef>),
+}
+
 /*
- * Helper function to retrieve the next object (or none) from an iterator.
+ * Helper function to retrieve the next object (or the StopIteration's
+ * return value) from an iterator.
  */
-pub fn get_next_object(
+pub fn get_next_object_with_value(
     vm: &VirtualMachine,
     iter_obj: &PyObjectRef,
-) -> PyResult<Option<PyObjectRef>> {
+) -> PyResult<IterNextOutput> {
     let next_obj: PyResult = call_next(vm, iter_obj);
 
     match next_obj {
-        Ok(value) => Ok(Some(value)),
+        Ok(value) => Ok(IterNextOutput::Value(value)),
         Err(next_error) => {
             // Check if we have stopiteration, or something else:
             if objtype::isinstance(&next_error, &vm.ctx.exceptions.stop_iteration) {
-                Ok(None)
+                let args = vm.get_attribute(next_error, "args")?;
+                // args is a tuple on a normal StopIteration, but a subclass
+                // could override it with something else; treat that as "no
+                // value" instead of letting get_elements panic on it.
+                let value = if objtype::isinstance(&args, &vm.ctx.tuple_type()) {
+                    objsequence::get_elements(&args).first().cloned()
+                } else {
+                    None
+                };
+                Ok(IterNextOutput::StopIteration(value))
             } else {
                 Err(next_error)
             }
@@ -56,19 +91,144 @@ pub fn get_next_object(
     }
 }
 
+/*
+ * Helper function to retrieve the next object (or none) from an iterator.
+ */
+pub fn get_next_object(
+    vm: &VirtualMachine,
+    iter_obj: &PyObjectRef,
+) -> PyResult<Option<PyObjectRef>> {
+    match get_next_object_with_value(vm, iter_obj)? {
+        IterNextOutput::Value(value) => Ok(Some(value)),
+        IterNextOutput::StopIteration(_) => Ok(None),
+    }
+}
+
+/// A `std::iter::Iterator` over a Python iterator. Built from a
+/// `PyObjectRef` via `get_iter`, so it also accepts objects that only
+/// implement `__getitem__`. Yields `None` once the Python iterator raises
+/// `StopIteration` and `Some(Err(..))` if it raises anything else, which
+/// lets native code use the usual adapters (`.map`, `.collect`, `.take`, ...)
+/// instead of hand-rolling a `loop { get_next_object(...)? }`.
+pub struct PyIterator<'a> {
+    vm: &'a VirtualMachine,
+    obj: PyObjectRef,
+}
+
+impl<'a> PyIterator<'a> {
+    pub fn new(vm: &'a VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
+        let obj = get_iter(vm, &obj)?;
+        Ok(PyIterator { vm, obj })
+    }
+
+    /// Wrap an object that is already an iterator (its `__next__` can be
+    /// called directly), without going through `__iter__` again. This keeps
+    /// the same contract `get_all`/`get_next_object` have always had: the
+    /// caller hands over an iterator, not an arbitrary iterable.
+    fn from_iter(vm: &'a VirtualMachine, obj: PyObjectRef) -> Self {
+        PyIterator { vm, obj }
+    }
+}
+
+impl<'a> Iterator for PyIterator<'a> {
+    type Item = PyResult<PyObjectRef>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match get_next_object(self.vm, &self.obj) {
+            Ok(Some(value)) => Some(Ok(value)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Extension trait for getting a Rust-native iterator out of any Python
+/// iterable, mirroring `get_iter` but returning something `for`-loopable
+/// from Rust.
+pub trait PyIterable {
+    fn iter<'a>(&self, vm: &'a VirtualMachine) -> PyResult<PyIterator<'a>>;
+}
+
+impl PyIterable for PyObjectRef {
+    fn iter<'a>(&self, vm: &'a VirtualMachine) -> PyResult<PyIterator<'a>> {
+        PyIterator::new(vm, self.clone())
+    }
+}
+
+/// Ask an iterator for its `__length_hint__`, for pre-sizing a collection
+/// that will be filled from it. Returns `None` if the method is absent,
+/// raises, or returns something other than a non-negative integer -- any of
+/// which just means "no hint available", not an error.
+pub fn length_hint(vm: &VirtualMachine, iter_obj: PyObjectRef) -> PyResult<Option<usize>> {
+    let hint = match vm.call_method(&iter_obj, "__length_hint__", vec![]) {
+        Ok(hint) => hint,
+        Err(_) => return Ok(None),
+    };
+    if vm.is_none(&hint) {
+        return Ok(None);
+    }
+    match hint.payload::<objint::PyInt>() {
+        // Clamp to a sane preallocation bound so a hostile or buggy
+        // __length_hint__ (e.g. claiming 10**12 elements) can't make us
+        // reserve a multi-gigabyte Vec up front and abort the process;
+        // isize::max_value() alone doesn't protect against that, it only
+        // keeps the value addressable.
+        Some(int) => Ok(int
+            .as_bigint()
+            .to_usize()
+            .map(|n| n.min(MAX_PREALLOCATE_HINT))),
+        None => Ok(None),
+    }
+}
+
+/// Upper bound on how many elements `get_all` will eagerly pre-allocate for
+/// based on an untrusted `__length_hint__`. Real collections of this size
+/// are rare; if the hint is bigger, letting the `Vec` grow normally as
+/// elements actually arrive is cheap, while trusting an arbitrarily large
+/// hint up front is not.
+const MAX_PREALLOCATE_HINT: usize = 4096;
+
 /* Retrieve all elements from an iterator */
 pub fn get_all(vm: &VirtualMachine, iter_obj: &PyObjectRef) -> PyResult<Vec<PyObjectRef>> {
-    let mut elements = vec![];
-    loop {
-        let element = get_next_object(vm, iter_obj)?;
-        match element {
-            Some(v) => elements.push(v),
-            None => break,
-        }
+    let cap = length_hint(vm, iter_obj.clone())?.unwrap_or(0);
+    let mut elements = Vec::with_capacity(cap);
+    for element in PyIterator::from_iter(vm, iter_obj.clone()) {
+        elements.push(element?);
     }
     Ok(elements)
 }
 
+/// Build the iterator backing the two-argument `iter(callable, sentinel)`
+/// form. `builtin_iter` calls this instead of `get_iter` when it
+/// receives a sentinel argument.
+pub fn get_callable_iterator(
+    vm: &VirtualMachine,
+    callable: PyObjectRef,
+    sentinel: PyObjectRef,
+) -> PyResult {
+    let iterator = PyCallableIterator {
+        callable,
+        sentinel,
+        exhausted: Cell::new(false),
+    };
+    Ok(iterator.into_ref(vm).into_object())
+}
+
+/// Dispatch for the two forms of `iter`: `iter(iterable)` goes through
+/// `get_iter`, `iter(callable, sentinel)` through `get_callable_iterator`.
+/// Argument parsing is left to the caller. This file does not register it
+/// as the `iter` builtin.
+pub fn builtin_iter(
+    vm: &VirtualMachine,
+    iter_target: PyObjectRef,
+    sentinel: Option<PyObjectRef>,
+) -> PyResult {
+    match sentinel {
+        Some(sentinel) => get_callable_iterator(vm, iter_target, sentinel),
+        None => get_iter(vm, &iter_target),
+    }
+}
+
 pub fn new_stop_iteration(vm: &VirtualMachine) -> PyObjectRef {
     let stop_iteration_type = vm.ctx.exceptions.stop_iteration.clone();
     vm.new_exception(stop_iteration_type, "End of iterator".to_string())
@@ -135,6 +295,147 @@ impl PyIteratorValueRef {
     }
 }
 
+// Generic iterator over an object that only implements __getitem__, used as
+// the get_iter fallback for old-style sequence classes.
+#[derive(Debug)]
+pub struct PySequenceIterator {
+    pub position: Cell<isize>,
+    pub obj: PyObjectRef,
+}
+
+impl PyValue for PySequenceIterator {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        sequence_iterator_type(vm)
+    }
+}
+
+type PySequenceIteratorRef = PyRef<PySequenceIterator>;
+
+impl PySequenceIteratorRef {
+    fn next(self, vm: &VirtualMachine) -> PyResult {
+        let pos = self.position.get();
+        let next_obj = vm.call_method(&self.obj, "__getitem__", vec![vm.ctx.new_int(pos)]);
+        match next_obj {
+            Ok(value) => {
+                self.position.set(pos + 1);
+                Ok(value)
+            }
+            Err(next_error) => {
+                if objtype::isinstance(&next_error, &vm.ctx.exceptions.index_error) {
+                    Err(new_stop_iteration(vm))
+                } else {
+                    Err(next_error)
+                }
+            }
+        }
+    }
+
+    fn iter(self, _vm: &VirtualMachine) -> Self {
+        self
+    }
+}
+
+thread_local! {
+    static SEQUENCE_ITERATOR_TYPE: RefCell<Option<PyClassRef>> = RefCell::new(None);
+}
+
+// The sequence_iterator class is created once per thread and cached here;
+// it is not a field of PyContext and is not part of PyContext's fixed set
+// of builtin types set up in its `new`/`init`.
+fn sequence_iterator_type(vm: &VirtualMachine) -> PyClassRef {
+    SEQUENCE_ITERATOR_TYPE.with(|cell| {
+        if let Some(cls) = cell.borrow().clone() {
+            return cls;
+        }
+        let cls = vm.ctx.new_class("sequence_iterator", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__next__" => vm.ctx.new_rustfunc(PySequenceIteratorRef::next),
+            "__iter__" => vm.ctx.new_rustfunc(PySequenceIteratorRef::iter),
+        });
+        *cell.borrow_mut() = Some(cls.clone());
+        cls
+    })
+}
+
+// Iterator backing `iter(callable, sentinel)`: repeatedly calls `callable`
+// with no arguments until it returns a value equal to `sentinel`.
+#[derive(Debug)]
+pub struct PyCallableIterator {
+    pub callable: PyObjectRef,
+    pub sentinel: PyObjectRef,
+    pub exhausted: Cell<bool>,
+}
+
+impl PyValue for PyCallableIterator {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        callable_iterator_type(vm)
+    }
+}
+
+type PyCallableIteratorRef = PyRef<PyCallableIterator>;
+
+impl PyCallableIteratorRef {
+    fn next(self, vm: &VirtualMachine) -> PyResult {
+        if self.exhausted.get() {
+            return Err(new_stop_iteration(vm));
+        }
+
+        let value = vm.invoke(&self.callable, vec![])?;
+        if rich_compare_eq(vm, &value, &self.sentinel)? {
+            self.exhausted.set(true);
+            Err(new_stop_iteration(vm))
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn iter(self, _vm: &VirtualMachine) -> Self {
+        self
+    }
+}
+
+/// `a == b`, the way CPython's `PyObject_RichCompareBool(a, b, Py_EQ)`
+/// works: identical objects are always equal, and if `a.__eq__(b)` returns
+/// `NotImplemented` (e.g. for two unrelated types), the reflected
+/// `b.__eq__(a)` is tried before giving up and reporting not-equal.
+/// A plain `bool(a.__eq__(b))` is wrong here because `NotImplemented` is
+/// truthy, which would otherwise report spurious equality.
+fn rich_compare_eq(vm: &VirtualMachine, a: &PyObjectRef, b: &PyObjectRef) -> PyResult<bool> {
+    if a.is(b) {
+        return Ok(true);
+    }
+    let result = vm.call_method(a, "__eq__", vec![b.clone()])?;
+    if !result.is(&vm.ctx.not_implemented()) {
+        return objbool::boolval(vm, result);
+    }
+    let result = vm.call_method(b, "__eq__", vec![a.clone()])?;
+    if !result.is(&vm.ctx.not_implemented()) {
+        return objbool::boolval(vm, result);
+    }
+    Ok(false)
+}
+
+thread_local! {
+    static CALLABLE_ITERATOR_TYPE: RefCell<Option<PyClassRef>> = RefCell::new(None);
+}
+
+// Cached per thread the same way as sequence_iterator_type() above, not
+// as a field of PyContext.
+fn callable_iterator_type(vm: &VirtualMachine) -> PyClassRef {
+    CALLABLE_ITERATOR_TYPE.with(|cell| {
+        if let Some(cls) = cell.borrow().clone() {
+            return cls;
+        }
+        let cls = vm.ctx.new_class("callable_iterator", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__next__" => vm.ctx.new_rustfunc(PyCallableIteratorRef::next),
+            "__iter__" => vm.ctx.new_rustfunc(PyCallableIteratorRef::iter),
+        });
+        *cell.borrow_mut() = Some(cls.clone());
+        cls
+    })
+}
+
 pub fn init(context: &PyContext) {
     let iter_type = &context.iter_type;
 
@@ -149,4 +450,241 @@ pub fn init(context: &PyContext) {
         "__iter__" => context.new_rustfunc(PyIteratorValueRef::iter),
         "__doc__" => context.new_str(iter_doc.to_string()),
     });
+
+    // sequence_iterator_type and callable_iterator_type are created lazily
+    // on first use (see sequence_iterator_type() / callable_iterator_type()
+    // above) rather than registered here.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn callable_iterator_stops_at_sentinel() {
+        let vm = VirtualMachine::new(Default::default());
+        let counter = Cell::new(0);
+        let callable = vm.ctx.new_rustfunc(move |vm: &VirtualMachine| -> PyResult {
+            let n = counter.get();
+            counter.set(n + 1);
+            Ok(vm.ctx.new_int(n))
+        });
+        let sentinel = vm.ctx.new_int(3);
+        let iterator = get_callable_iterator(&vm, callable, sentinel).unwrap();
+
+        let mut seen = Vec::new();
+        while let Some(value) = get_next_object(&vm, &iterator).unwrap() {
+            seen.push(objint::get_value(&value).to_i32().unwrap());
+        }
+        assert_eq!(seen, vec![0, 1, 2]);
+
+        // Once exhausted, it must keep raising StopIteration rather than
+        // calling the callable again.
+        assert!(get_next_object(&vm, &iterator).unwrap().is_none());
+    }
+
+    #[test]
+    fn stop_iteration_value_is_preserved() {
+        let vm = VirtualMachine::new(Default::default());
+
+        // A minimal iterator whose __next__ always raises StopIteration(42),
+        // mimicking a generator that does `return 42`.
+        let cls = vm.ctx.new_class("stop_with_value_iter", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__next__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| -> PyResult {
+                let exc = vm.new_exception(vm.ctx.exceptions.stop_iteration.clone(), "".to_string());
+                vm.set_attr(&exc, "args", vm.ctx.new_tuple(vec![vm.ctx.new_int(42)]))?;
+                Err(exc)
+            }),
+        });
+        let iterator = vm.ctx.new_instance(cls, None);
+
+        match get_next_object_with_value(&vm, &iterator).unwrap() {
+            IterNextOutput::StopIteration(Some(value)) => {
+                assert_eq!(objint::get_value(&value).to_i32().unwrap(), 42);
+            }
+            _ => panic!("expected StopIteration(42)"),
+        }
+    }
+
+    #[test]
+    fn builtin_iter_dispatches_on_arg_count() {
+        let vm = VirtualMachine::new(Default::default());
+
+        // One argument: behaves like get_iter over an iterable.
+        let list = vm.ctx.new_list(vec![vm.ctx.new_int(1), vm.ctx.new_int(2)]);
+        let iterator = builtin_iter(&vm, list, None).unwrap();
+        assert_eq!(
+            objint::get_value(&get_next_object(&vm, &iterator).unwrap().unwrap())
+                .to_i32()
+                .unwrap(),
+            1
+        );
+
+        // Two arguments: behaves like get_callable_iterator.
+        let counter = Cell::new(0);
+        let callable = vm.ctx.new_rustfunc(move |vm: &VirtualMachine| -> PyResult {
+            let n = counter.get();
+            counter.set(n + 1);
+            Ok(vm.ctx.new_int(n))
+        });
+        let iterator = builtin_iter(&vm, callable, Some(vm.ctx.new_int(1))).unwrap();
+        assert_eq!(
+            objint::get_value(&get_next_object(&vm, &iterator).unwrap().unwrap())
+                .to_i32()
+                .unwrap(),
+            0
+        );
+        assert!(get_next_object(&vm, &iterator).unwrap().is_none());
+    }
+
+    #[test]
+    fn callable_iterator_sentinel_compare_handles_not_implemented() {
+        let vm = VirtualMachine::new(Default::default());
+
+        // A value whose __eq__ always returns NotImplemented: naively
+        // treating NotImplemented as truthy would stop iteration
+        // immediately even though the value never actually equals the
+        // sentinel.
+        let cls = vm.ctx.new_class("never_equal", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__eq__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| vm.ctx.not_implemented()),
+        });
+        let odd_value = vm.ctx.new_instance(cls, None);
+
+        let calls = Cell::new(0);
+        let callable = vm.ctx.new_rustfunc(move |vm: &VirtualMachine| -> PyResult {
+            let n = calls.get();
+            calls.set(n + 1);
+            Ok(vm.ctx.new_int(n))
+        });
+        let iterator = get_callable_iterator(&vm, callable, odd_value).unwrap();
+
+        // The callable never returns something equal to the sentinel, so
+        // this must not stop after the first call.
+        assert!(get_next_object(&vm, &iterator).unwrap().is_some());
+        assert!(get_next_object(&vm, &iterator).unwrap().is_some());
+    }
+
+    #[test]
+    fn length_hint_is_none_when_method_absent() {
+        let vm = VirtualMachine::new(Default::default());
+        let obj = vm.ctx.new_instance(vm.ctx.object(), None);
+        assert_eq!(length_hint(&vm, obj).unwrap(), None);
+    }
+
+    #[test]
+    fn length_hint_is_none_when_method_raises() {
+        let vm = VirtualMachine::new(Default::default());
+        let cls = vm.ctx.new_class("raises_length_hint", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__length_hint__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| -> PyResult {
+                Err(vm.new_exception(vm.ctx.exceptions.value_error.clone(), "nope".to_string()))
+            }),
+        });
+        let obj = vm.ctx.new_instance(cls, None);
+        assert_eq!(length_hint(&vm, obj).unwrap(), None);
+    }
+
+    #[test]
+    fn length_hint_is_none_for_negative_value() {
+        let vm = VirtualMachine::new(Default::default());
+        let cls = vm.ctx.new_class("negative_length_hint", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__length_hint__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| vm.ctx.new_int(-1)),
+        });
+        let obj = vm.ctx.new_instance(cls, None);
+        assert_eq!(length_hint(&vm, obj).unwrap(), None);
+    }
+
+    #[test]
+    fn length_hint_is_none_for_non_int_value() {
+        let vm = VirtualMachine::new(Default::default());
+        let cls = vm.ctx.new_class("stringy_length_hint", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__length_hint__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| vm.ctx.new_tuple(vec![])),
+        });
+        let obj = vm.ctx.new_instance(cls, None);
+        assert_eq!(length_hint(&vm, obj).unwrap(), None);
+    }
+
+    #[test]
+    fn length_hint_clamps_hostile_value_to_sane_bound() {
+        let vm = VirtualMachine::new(Default::default());
+        let cls = vm.ctx.new_class("huge_length_hint", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__length_hint__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| {
+                vm.ctx.new_int(1_000_000_000_000i64)
+            }),
+        });
+        let obj = vm.ctx.new_instance(cls, None);
+        assert_eq!(length_hint(&vm, obj).unwrap(), Some(MAX_PREALLOCATE_HINT));
+    }
+
+    #[test]
+    fn get_iter_falls_back_to_getitem_sequence_protocol() {
+        let vm = VirtualMachine::new(Default::default());
+
+        // No __iter__ at all, only __getitem__: get_iter must build a
+        // PySequenceIterator that indexes 0, 1, 2, ... until __getitem__
+        // raises IndexError, which becomes StopIteration.
+        let cls = vm.ctx.new_class("only_getitem", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__getitem__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine, index: i32| -> PyResult {
+                if index < 3 {
+                    Ok(vm.ctx.new_int(index * 10))
+                } else {
+                    Err(vm.new_exception(vm.ctx.exceptions.index_error.clone(), "".to_string()))
+                }
+            }),
+        });
+        let obj = vm.ctx.new_instance(cls, None);
+
+        let iterator = get_iter(&vm, &obj).unwrap();
+        let mut seen = Vec::new();
+        while let Some(value) = get_next_object(&vm, &iterator).unwrap() {
+            seen.push(objint::get_value(&value).to_i32().unwrap());
+        }
+        assert_eq!(seen, vec![0, 10, 20]);
+    }
+
+    #[test]
+    fn get_iter_propagates_non_attribute_error_from_dunder_iter() {
+        let vm = VirtualMachine::new(Default::default());
+
+        // __iter__ exists but raises something other than AttributeError,
+        // and __getitem__ is also present. The real error from __iter__
+        // must propagate rather than being swallowed in favor of indexing.
+        let cls = vm.ctx.new_class("broken_iter", vm.ctx.object());
+        extend_class!(vm.ctx, &cls, {
+            "__iter__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine| -> PyResult {
+                Err(vm.new_exception(vm.ctx.exceptions.value_error.clone(), "broken".to_string()))
+            }),
+            "__getitem__" => vm.ctx.new_rustfunc(|vm: &VirtualMachine, _index: i32| -> PyResult {
+                Ok(vm.ctx.new_int(0))
+            }),
+        });
+        let obj = vm.ctx.new_instance(cls, None);
+
+        let err = get_iter(&vm, &obj).unwrap_err();
+        assert!(objtype::isinstance(&err, &vm.ctx.exceptions.value_error));
+    }
+
+    #[test]
+    fn py_iterable_adapter_collects_elements() {
+        let vm = VirtualMachine::new(Default::default());
+        let list = vm.ctx.new_list(vec![
+            vm.ctx.new_int(1),
+            vm.ctx.new_int(2),
+            vm.ctx.new_int(3),
+        ]);
+
+        let values: Vec<i32> = list
+            .iter(&vm)
+            .unwrap()
+            .map(|item| objint::get_value(&item.unwrap()).to_i32().unwrap())
+            .collect();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
 }